@@ -16,6 +16,11 @@
 
 use crate::{Identifier, Record, Type};
 use snarkvm_circuits_types::prelude::*;
+use std::io::{Read, Result as IoResult, Write};
+
+/// The maximum length of an array annotation, bounding the number of circuit elements
+/// a single `[inner; len]` annotation can allocate.
+pub const MAX_ARRAY_LENGTH: usize = 32;
 
 /// An annotation defines the type parameters for a function or template.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -26,28 +31,112 @@ pub enum Annotation<E: Environment> {
     /// A composite annotation contains its identifier.
     /// The format of the annotation is `<identifier>`.
     Composite(Identifier<E>),
-    /// A record annotation contains its identifier of "record".
-    /// The format of the annotation is `record`.
-    Record,
+    /// A record annotation contains the identifier of the named record it refers to.
+    /// The format of the annotation is `record <identifier>`.
+    Record(Identifier<E>),
+    /// An array annotation contains its element annotation and its fixed length.
+    /// The format of the annotation is `[<annotation>; <length>]`.
+    Array(Box<Annotation<E>>, usize),
 }
 
 impl<E: Environment> Annotation<E> {
     /// Returns `true` if the annotation is a literal.
-    /// Returns `false` if the annotation is a composite or record.
+    /// Returns `false` if the annotation is a composite, record, or array.
     pub fn is_literal(&self) -> bool {
         matches!(self, Annotation::Literal(..))
     }
 
     /// Returns `true` if the annotation is a composite.
-    /// Returns `false` if the annotation is a literal or record.
+    /// Returns `false` if the annotation is a literal, record, or array.
     pub fn is_composite(&self) -> bool {
         matches!(self, Annotation::Composite(..))
     }
 
     /// Returns `true` if the annotation is a record.
-    /// Returns `false` if the annotation is a literal or composite.
+    /// Returns `false` if the annotation is a literal, composite, or array.
     pub fn is_record(&self) -> bool {
-        matches!(self, Annotation::Record)
+        matches!(self, Annotation::Record(..))
+    }
+
+    /// Returns `true` if the annotation is an array.
+    /// Returns `false` if the annotation is a literal, composite, or record.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Annotation::Array(..))
+    }
+}
+
+/// The primitive type names that a `Literal` annotation may be composed from.
+///
+/// `Type` has no API for enumerating its own variants, so this list is hand-maintained in
+/// parallel with it. `test_literal_type_names_match_type` guards against drift: if a name here
+/// stops round-tripping through `Type::parse`, the filter in `completions` would otherwise drop
+/// it silently, so that test turns the mismatch into a test failure instead.
+const LITERAL_TYPE_NAMES: &[&str] =
+    &["address", "boolean", "field", "group", "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128", "scalar", "string"];
+
+/// The modes that may be crossed with a primitive type name to form a literal annotation.
+const LITERAL_MODES: &[Mode] = &[Mode::Constant, Mode::Public, Mode::Private];
+
+impl<E: Environment> Annotation<E> {
+    /// Returns the ranked list of candidate annotation strings for the given `prefix`.
+    ///
+    /// This mirrors [`Self::completions`], but returns the display form of each candidate
+    /// rather than a constructed `Annotation`, which is what an editor typically renders in
+    /// its completion popup.
+    pub fn candidate_strings(prefix: &str, known_composites: &[Identifier<E>]) -> Vec<String> {
+        Self::completions(prefix, known_composites).iter().map(|annotation| annotation.to_string()).collect()
+    }
+
+    /// Returns the ranked list of annotation completions for the given `prefix`, given the
+    /// composite identifiers known to be in scope.
+    ///
+    /// Literal completions are produced by crossing every primitive type name with every mode,
+    /// e.g. `field.private`. Composite completions surface any `known_composites` whose name
+    /// matches the prefix. A prefix matching the `record` keyword surfaces the
+    /// `known_composites` as candidate record names. Matching is substring-based (a prefix
+    /// such as `ield` still surfaces `field.*`), not anchored to the start of the candidate.
+    /// Results are deduplicated and ordered literals, then composites, then records, mirroring
+    /// how completion crates group kinds.
+    pub fn completions(prefix: &str, known_composites: &[Identifier<E>]) -> Vec<Self> {
+        let matches = |candidate: &str| candidate.contains(prefix);
+
+        // Enumerate the literal completions by crossing every primitive type name with every mode.
+        let mut literals: Vec<Self> = LITERAL_TYPE_NAMES
+            .iter()
+            .flat_map(|type_name| LITERAL_MODES.iter().map(move |mode| format!("{type_name}.{mode}")))
+            .filter(|candidate| matches(candidate))
+            .filter_map(|candidate| Type::parse(&candidate).ok())
+            .map(|(_, type_)| Self::Literal(type_))
+            .collect();
+        literals.dedup();
+
+        // Surface the known composites whose name matches the prefix.
+        let mut composites: Vec<Self> = known_composites
+            .iter()
+            .filter(|identifier| matches(&identifier.to_string()))
+            .cloned()
+            .map(Self::Composite)
+            .collect();
+        let mut seen = Vec::new();
+        composites.retain(|candidate| !seen.contains(candidate) && {
+            seen.push(candidate.clone());
+            true
+        });
+
+        // Surface the known composites as candidate record names, when the prefix matches the
+        // `record` keyword, e.g. typing `rec` suggests naming a record after each known composite.
+        let mut records: Vec<Self> = if matches(Record::<E>::type_name()) {
+            known_composites.iter().cloned().map(Self::Record).collect()
+        } else {
+            Vec::new()
+        };
+        let mut seen = Vec::new();
+        records.retain(|candidate| !seen.contains(candidate) && {
+            seen.push(candidate.clone());
+            true
+        });
+
+        literals.into_iter().chain(composites).chain(records).collect()
     }
 }
 
@@ -57,11 +146,33 @@ impl<E: Environment> Parser for Annotation<E> {
     /// Parses a string into an annotation.
     #[inline]
     fn parse(string: &str) -> ParserResult<Self> {
-        // Parse to determine the annotation (order matters).
+        // Parse to determine the annotation (order matters; the array form must be tried
+        // before the scalar forms so that a leading `[` is not misinterpreted).
         alt((
+            map_res(
+                tuple((
+                    tag("["),
+                    Self::parse,
+                    tag(";"),
+                    tag(" "),
+                    map_res(digit1, |length: &str| length.parse::<usize>()),
+                    tag("]"),
+                )),
+                |(_, inner, _, _, length, _)| {
+                    // Reject an empty array, and cap the length to keep circuits bounded.
+                    match length {
+                        0 => Err("array annotation must have a nonzero length"),
+                        length if length > MAX_ARRAY_LENGTH => Err("array annotation exceeds the maximum length"),
+                        length => Ok(Self::Array(Box::new(inner), length)),
+                    }
+                },
+            ),
             map(Type::parse, |type_| Self::Literal(type_)),
+            map(
+                pair(pair(tag(Record::<E>::type_name()), tag(" ")), Identifier::parse),
+                |(_, identifier)| Self::Record(identifier),
+            ),
             map(Identifier::parse, |identifier| Self::Composite(identifier)),
-            map(tag(Record::<E>::type_name()), |_| Self::Record),
         ))(string)
     }
 }
@@ -74,12 +185,175 @@ impl<E: Environment> fmt::Display for Annotation<E> {
             Self::Literal(type_) => fmt::Display::fmt(type_, f),
             // Prints the composite type, i.e. signature
             Self::Composite(identifier) => fmt::Display::fmt(identifier, f),
-            // Prints the record type, i.e. record
-            Self::Record => write!(f, "{}", Record::<E>::type_name()),
+            // Prints the named record type, i.e. record Token
+            Self::Record(identifier) => write!(f, "{} {}", Record::<E>::type_name(), identifier),
+            // Prints the array type, i.e. [field.private; 32]
+            Self::Array(inner, length) => write!(f, "[{inner}; {length}]"),
+        }
+    }
+}
+
+/// The one-byte discriminant identifying an `Annotation` variant in its binary encoding.
+/// New variants must be assigned a new discriminant rather than reusing an existing one.
+const LITERAL_DISCRIMINANT: u8 = 0;
+const COMPOSITE_DISCRIMINANT: u8 = 1;
+const RECORD_DISCRIMINANT: u8 = 2;
+const ARRAY_DISCRIMINANT: u8 = 3;
+
+impl<E: Environment> ToBytes for Annotation<E> {
+    /// Writes the annotation to a byte stream, as a one-byte discriminant followed by the
+    /// variant's payload. This is a canonical wire encoding distinct from the display grammar.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Literal(type_) => {
+                LITERAL_DISCRIMINANT.write_le(&mut writer)?;
+                type_.write_le(&mut writer)
+            }
+            Self::Composite(identifier) => {
+                COMPOSITE_DISCRIMINANT.write_le(&mut writer)?;
+                identifier.write_le(&mut writer)
+            }
+            Self::Record(identifier) => {
+                RECORD_DISCRIMINANT.write_le(&mut writer)?;
+                identifier.write_le(&mut writer)
+            }
+            Self::Array(inner, length) => {
+                ARRAY_DISCRIMINANT.write_le(&mut writer)?;
+                inner.write_le(&mut writer)?;
+                (*length as u32).write_le(&mut writer)
+            }
         }
     }
 }
 
+impl<E: Environment> FromBytes for Annotation<E> {
+    /// Reads the annotation from a byte stream, rejecting unknown discriminants and truncated
+    /// or out-of-range payloads.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        match u8::read_le(&mut reader)? {
+            LITERAL_DISCRIMINANT => Ok(Self::Literal(Type::read_le(&mut reader)?)),
+            COMPOSITE_DISCRIMINANT => Ok(Self::Composite(Identifier::read_le(&mut reader)?)),
+            RECORD_DISCRIMINANT => Ok(Self::Record(Identifier::read_le(&mut reader)?)),
+            ARRAY_DISCRIMINANT => {
+                let inner = Self::read_le(&mut reader)?;
+                let length = u32::read_le(&mut reader)? as usize;
+                if length == 0 || length > MAX_ARRAY_LENGTH {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "array annotation length is zero or exceeds the maximum length",
+                    ));
+                }
+                Ok(Self::Array(Box::new(inner), length))
+            }
+            discriminant => {
+                Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid annotation discriminant {discriminant}")))
+            }
+        }
+    }
+}
+
+/// A linting pass over the annotations attached to a single function or template, producing
+/// structured diagnostics rather than failing outright on the first malformed annotation.
+pub mod lint {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// The severity of a lint diagnostic.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Severity {
+        /// A lint that indicates the program is invalid.
+        Error,
+        /// A lint that indicates the program is likely, but not certainly, to contain a mistake.
+        Warning,
+    }
+
+    /// A single diagnostic produced by the annotation linter, keyed to the offending
+    /// identifier or type so that a caller can point the user at the source of the problem.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct LintResult {
+        /// The severity of the diagnostic.
+        pub severity: Severity,
+        /// The human-readable description of the diagnostic.
+        pub message: String,
+    }
+
+    impl LintResult {
+        /// Constructs an error-severity diagnostic with the given message.
+        fn error(message: impl Into<String>) -> Self {
+            Self { severity: Severity::Error, message: message.into() }
+        }
+
+        /// Constructs a warning-severity diagnostic with the given message.
+        fn warning(message: impl Into<String>) -> Self {
+            Self { severity: Severity::Warning, message: message.into() }
+        }
+    }
+
+    /// Lints the annotations attached to a single function or template.
+    ///
+    /// `known_composites` is the set of identifiers defined as templates in the program, and
+    /// `known_records` is the set of identifiers defined as records, together with their
+    /// declared members, used to check the canonical record interface (see
+    /// [`Record::validate_interface`]).
+    ///
+    /// This models three kinds of checks, mirroring the "no-duplicates", "single-may-exist",
+    /// and "single-must-exist" rules used elsewhere when validating a set of declarations:
+    ///   - no-duplicates: a composite annotation must not name the same identifier twice.
+    ///   - single-may-exist: a composite or record annotation must name an identifier that is
+    ///     actually defined in the program as a template or record, and a record annotation's
+    ///     backing record must satisfy the canonical record interface.
+    ///   - single-must-exist: a function must declare at least one output annotation.
+    pub fn lint_annotations<E: Environment>(
+        inputs: &[Annotation<E>],
+        outputs: &[Annotation<E>],
+        known_composites: &[Identifier<E>],
+        known_records: &[(Identifier<E>, Vec<(Identifier<E>, Mode)>)],
+    ) -> Vec<LintResult> {
+        let mut results = Vec::new();
+        let annotations = || inputs.iter().chain(outputs.iter());
+
+        // No-duplicates: a composite annotation must not name the same identifier twice.
+        let mut seen = HashSet::new();
+        for identifier in annotations().filter_map(|annotation| match annotation {
+            Annotation::Composite(identifier) => Some(identifier),
+            _ => None,
+        }) {
+            if !seen.insert(identifier) {
+                results.push(LintResult::error(format!("duplicate composite annotation `{identifier}`")));
+            }
+        }
+
+        // Single-may-exist: a composite or record annotation must refer to something defined.
+        for annotation in annotations() {
+            match annotation {
+                Annotation::Composite(identifier) if !known_composites.contains(identifier) => {
+                    results.push(LintResult::error(format!("`{identifier}` is not defined as a template")));
+                }
+                Annotation::Record(identifier) => match known_records.iter().find(|(name, _)| name == identifier) {
+                    Some((_, members)) => {
+                        if let Err(issue) = Record::<E>::validate_interface(members) {
+                            results.push(LintResult::error(format!("record `{identifier}` {issue}")));
+                        }
+                    }
+                    None => {
+                        results.push(LintResult::error(format!("`{identifier}` is not defined as a record")));
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        // Single-must-exist: a function should declare at least one output annotation. This is
+        // a warning rather than an error, since a function with no outputs (e.g. one invoked
+        // purely for its side effects) is unusual but not inherently invalid.
+        if outputs.is_empty() {
+            results.push(LintResult::warning("function declares no output annotation"));
+        }
+
+        results
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,7 +365,28 @@ mod tests {
     fn test_annotation_parse() {
         assert_eq!(Annotation::parse("field.private"), Ok(("", Annotation::<E>::Literal(Type::Field(Mode::Private)))));
         assert_eq!(Annotation::parse("signature"), Ok(("", Annotation::<E>::Composite(Identifier::new("signature")))));
-        assert_eq!(Annotation::parse("record"), Ok(("", Annotation::<E>::Record)));
+        assert_eq!(
+            Annotation::parse("record Token"),
+            Ok(("", Annotation::<E>::Record(Identifier::new("Token"))))
+        );
+        assert_eq!(
+            Annotation::parse("[field.private; 32]"),
+            Ok(("", Annotation::<E>::Array(Box::new(Annotation::<E>::Literal(Type::Field(Mode::Private))), 32)))
+        );
+        // Arrays nest over other arrays and over composites.
+        assert_eq!(
+            Annotation::parse("[[signature; 4]; 2]"),
+            Ok((
+                "",
+                Annotation::<E>::Array(
+                    Box::new(Annotation::<E>::Array(
+                        Box::new(Annotation::<E>::Composite(Identifier::new("signature"))),
+                        4
+                    )),
+                    2
+                )
+            ))
+        );
     }
 
     #[test]
@@ -102,5 +397,168 @@ mod tests {
         assert_eq!(Ok((".private", Identifier::<E>::new("signature"))), Identifier::<E>::parse("signature.private"));
         // Record must not contain visibility.
         assert!(Identifier::<E>::parse("record.private").is_err());
+        // A record annotation must name the record it refers to.
+        assert!(Annotation::<E>::parse("record").is_err());
+        // An array annotation must not have a zero length.
+        assert!(Annotation::<E>::parse("[field.private; 0]").is_err());
+        // An array annotation must not exceed the maximum length.
+        assert!(Annotation::<E>::parse("[field.private; 33]").is_err());
+    }
+
+    #[test]
+    fn test_completions() {
+        let known_composites = [Identifier::<E>::new("record"), Identifier::<E>::new("receipt")];
+
+        // A literal prefix yields every mode of the matching primitive type.
+        assert_eq!(
+            Annotation::<E>::completions("fie", &known_composites),
+            vec![
+                Annotation::<E>::Literal(Type::Field(Mode::Constant)),
+                Annotation::<E>::Literal(Type::Field(Mode::Public)),
+                Annotation::<E>::Literal(Type::Field(Mode::Private)),
+            ]
+        );
+
+        // A composite prefix surfaces the matching known composites.
+        assert_eq!(
+            Annotation::<E>::completions("rece", &known_composites),
+            vec![Annotation::<E>::Composite(Identifier::new("receipt"))]
+        );
+
+        // A prefix of the `record` keyword surfaces record completions over the known composites.
+        assert_eq!(
+            Annotation::<E>::completions("rec", &known_composites),
+            vec![
+                Annotation::<E>::Composite(Identifier::new("record")),
+                Annotation::<E>::Composite(Identifier::new("receipt")),
+                Annotation::<E>::Record(Identifier::new("record")),
+                Annotation::<E>::Record(Identifier::new("receipt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_candidate_strings() {
+        let known_composites = [Identifier::<E>::new("signature")];
+        assert_eq!(
+            Annotation::<E>::candidate_strings("fie", &known_composites),
+            vec!["field.constant", "field.public", "field.private"]
+        );
+    }
+
+    #[test]
+    fn test_completions_match_interior_substrings() {
+        let known_composites = [Identifier::<E>::new("signature")];
+
+        // A substring that is not a prefix still surfaces the matching literal.
+        assert_eq!(
+            Annotation::<E>::completions("ield", &known_composites),
+            vec![
+                Annotation::<E>::Literal(Type::Field(Mode::Constant)),
+                Annotation::<E>::Literal(Type::Field(Mode::Public)),
+                Annotation::<E>::Literal(Type::Field(Mode::Private)),
+            ]
+        );
+
+        // A substring that is not a prefix still surfaces the matching composite.
+        assert_eq!(
+            Annotation::<E>::completions("gnat", &known_composites),
+            vec![Annotation::<E>::Composite(Identifier::new("signature"))]
+        );
+    }
+
+    #[test]
+    fn test_literal_type_names_match_type() {
+        // Guards against `LITERAL_TYPE_NAMES` drifting out of sync with `Type`: if a name here
+        // no longer round-trips through `Type::parse`, `completions` would otherwise drop it
+        // silently, so fail loudly here instead.
+        for type_name in LITERAL_TYPE_NAMES {
+            for mode in LITERAL_MODES {
+                let candidate = format!("{type_name}.{mode}");
+                assert!(Type::<E>::parse(&candidate).is_ok(), "`{candidate}` failed to parse as a `Type`");
+            }
+        }
+    }
+
+    #[test]
+    fn test_lint_annotations() {
+        use lint::{lint_annotations, Severity};
+
+        let known_composites = [Identifier::<E>::new("signature")];
+        let valid_record_members =
+            vec![(Identifier::new("owner"), Mode::Private), (Identifier::new("gates"), Mode::Public), (Identifier::new("_nonce"), Mode::Public)];
+        let missing_member_record_members = vec![(Identifier::new("owner"), Mode::Private)];
+        let private_nonce_record_members =
+            vec![(Identifier::new("owner"), Mode::Private), (Identifier::new("gates"), Mode::Public), (Identifier::new("_nonce"), Mode::Private)];
+        let known_records = [
+            (Identifier::<E>::new("Token"), valid_record_members),
+            (Identifier::<E>::new("Malformed"), missing_member_record_members),
+            (Identifier::<E>::new("PrivateNonce"), private_nonce_record_members),
+        ];
+
+        // A well-formed function has no lint results.
+        let inputs = vec![Annotation::<E>::Composite(Identifier::new("signature"))];
+        let outputs = vec![Annotation::<E>::Record(Identifier::new("Token"))];
+        assert!(lint_annotations(&inputs, &outputs, &known_composites, &known_records).is_empty());
+
+        // A function with no output annotation is flagged, as a warning rather than an error.
+        let results = lint_annotations(&inputs, &[], &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].severity, Severity::Warning);
+
+        // A duplicate composite annotation is flagged.
+        let inputs =
+            vec![Annotation::<E>::Composite(Identifier::new("signature")), Annotation::<E>::Composite(Identifier::new("signature"))];
+        let results = lint_annotations(&inputs, &outputs, &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].severity, Severity::Error);
+
+        // A composite annotation naming an undefined identifier is flagged.
+        let inputs = vec![Annotation::<E>::Composite(Identifier::new("undefined"))];
+        let results = lint_annotations(&inputs, &outputs, &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+
+        // A record annotation whose backing record is missing a mandatory member is flagged,
+        // and the message says so rather than claiming a visibility problem.
+        let outputs = vec![Annotation::<E>::Record(Identifier::new("Malformed"))];
+        let results = lint_annotations(&[], &outputs, &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].message.contains("missing"));
+
+        // A record annotation whose `_nonce` is not public is flagged, and the message says so
+        // rather than claiming a member is missing.
+        let outputs = vec![Annotation::<E>::Record(Identifier::new("PrivateNonce"))];
+        let results = lint_annotations(&[], &outputs, &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].message.contains("missing"));
+        assert!(results[0].message.contains("public"));
+
+        // A record annotation naming an undefined record is flagged.
+        let outputs = vec![Annotation::<E>::Record(Identifier::new("Undefined"))];
+        let results = lint_annotations(&[], &outputs, &known_composites, &known_records);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_annotation_bytes() {
+        let candidates = vec![
+            Annotation::<E>::Literal(Type::Field(Mode::Private)),
+            Annotation::<E>::Composite(Identifier::new("signature")),
+            Annotation::<E>::Record(Identifier::new("Token")),
+            Annotation::<E>::Array(Box::new(Annotation::<E>::Literal(Type::Field(Mode::Private))), 32),
+        ];
+
+        for candidate in candidates {
+            let bytes = candidate.to_bytes_le().unwrap();
+            assert_eq!(Annotation::<E>::from_bytes_le(&bytes).unwrap(), candidate);
+        }
+    }
+
+    #[test]
+    fn test_annotation_from_bytes_fails() {
+        // An unknown discriminant is rejected.
+        assert!(Annotation::<E>::from_bytes_le(&[255]).is_err());
+        // A truncated payload is rejected.
+        assert!(Annotation::<E>::from_bytes_le(&[COMPOSITE_DISCRIMINANT]).is_err());
     }
 }
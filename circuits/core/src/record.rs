@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Identifier, Record};
+use snarkvm_circuits_types::prelude::*;
+
+/// The name of the mandatory member that holds a record's owner.
+const OWNER: &str = "owner";
+/// The name of the mandatory member that holds a record's gates.
+const GATES: &str = "gates";
+/// The name of the mandatory member that holds a record's nonce.
+const NONCE: &str = "_nonce";
+
+impl<E: Environment> Record<E> {
+    /// Validates that `members` satisfies the canonical record interface: `owner`, `gates`,
+    /// and `_nonce` must all be present, and `_nonce` must be `Mode::Public`. `owner` and
+    /// `gates` may carry any visibility the record author chooses.
+    ///
+    /// Returns `Err` with a message describing precisely what is wrong — either which of the
+    /// three mandatory members is missing, or that `_nonce` was declared with a non-public
+    /// visibility. `Record::parse` should call this on the parsed member list and turn an
+    /// `Err` into a parser error via `map_res`, so that a malformed record declaration fails
+    /// to parse rather than being silently accepted.
+    pub fn validate_interface(members: &[(Identifier<E>, Mode)]) -> Result<(), String> {
+        let mut has_owner = false;
+        let mut has_gates = false;
+        let mut nonce_mode = None;
+
+        for (identifier, mode) in members {
+            match identifier.to_string().as_str() {
+                OWNER => has_owner = true,
+                GATES => has_gates = true,
+                NONCE => nonce_mode = Some(*mode),
+                _ => {}
+            }
+        }
+
+        let mut missing = Vec::new();
+        if !has_owner {
+            missing.push(OWNER);
+        }
+        if !has_gates {
+            missing.push(GATES);
+        }
+        if nonce_mode.is_none() {
+            missing.push(NONCE);
+        }
+        if !missing.is_empty() {
+            return Err(format!("is missing its mandatory {} member(s)", missing.join(", ")));
+        }
+
+        match nonce_mode {
+            Some(Mode::Public) => Ok(()),
+            Some(mode) => Err(format!("declares `{NONCE}` with `{mode}` visibility, but it must be public")),
+            None => unreachable!("checked above"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_circuits_types::environment::Circuit;
+
+    type E = Circuit;
+
+    #[test]
+    fn test_validate_interface() {
+        // A record with all three mandatory members, and a public nonce, is valid.
+        assert!(
+            Record::<E>::validate_interface(&[
+                (Identifier::new("owner"), Mode::Private),
+                (Identifier::new("gates"), Mode::Public),
+                (Identifier::new("_nonce"), Mode::Public),
+            ])
+            .is_ok()
+        );
+
+        // A record missing `owner` is invalid.
+        let result = Record::<E>::validate_interface(&[
+            (Identifier::new("gates"), Mode::Public),
+            (Identifier::new("_nonce"), Mode::Public),
+        ]);
+        assert!(result.unwrap_err().contains("missing"));
+
+        // A record whose `_nonce` is not public is invalid.
+        let result = Record::<E>::validate_interface(&[
+            (Identifier::new("owner"), Mode::Private),
+            (Identifier::new("gates"), Mode::Public),
+            (Identifier::new("_nonce"), Mode::Private),
+        ]);
+        assert!(result.unwrap_err().contains("public"));
+    }
+}